@@ -1,7 +1,11 @@
+use crate::rate_limit::RateLimiter;
 use crate::{Session, Store, qs_params, Messages, CallbackProvider};
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use csv::{Reader, StringRecord, Writer};
 use http::Method;
 use serde::{Deserialize, Serialize, Deserializer};
+use std::io::{Read, Write as IoWrite};
 use std::sync::Arc;
 
 // Custom deserializer for ah_flag that can handle both string and bool
@@ -27,13 +31,78 @@ where
     }
 }
 
+/// Converts one of E*TRADE's epoch-seconds fields (`dateTimeUTC`,
+/// `expirationDate`, `timeOfLastTrade`, ...) into a `DateTime<Utc>`.
+fn epoch_seconds_to_utc(epoch: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(epoch, 0)
+}
+
+/// Offset of the US timezone abbreviations E*TRADE embeds in `dateTime`
+/// strings (e.g. `"12:00:00 EDT 01-01-2024"`).
+fn us_timezone_offset(abbr: &str) -> Option<FixedOffset> {
+    let hours = match abbr {
+        "EST" => -5,
+        "EDT" => -4,
+        "CST" => -6,
+        "CDT" => -5,
+        "MST" => -7,
+        "MDT" => -6,
+        "PST" => -8,
+        "PDT" => -7,
+        _ => return None,
+    };
+    FixedOffset::east_opt(hours * 3600)
+}
+
+/// Parses an E*TRADE `dateTime` string of the form `"HH:MM:SS TZ MM-DD-YYYY"`
+/// (e.g. `"12:00:00 EDT 01-01-2024"`) into a timezone-aware timestamp.
+pub fn parse_etrade_date_time(value: &str) -> Result<DateTime<FixedOffset>> {
+    let mut parts = value.splitn(3, ' ');
+    let time_part = parts.next().filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("missing time in date_time {:?}", value))?;
+    let tz_abbr = parts.next().filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("missing timezone in date_time {:?}", value))?;
+    let date_part = parts.next().filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("missing date in date_time {:?}", value))?;
+
+    let offset = us_timezone_offset(tz_abbr)
+        .ok_or_else(|| anyhow!("unrecognized timezone abbreviation {:?} in date_time {:?}", tz_abbr, value))?;
+
+    let naive_date = NaiveDate::parse_from_str(date_part, "%m-%d-%Y")
+        .map_err(|e| anyhow!("invalid date {:?} in date_time {:?}: {}", date_part, value, e))?;
+    let naive_time = NaiveTime::parse_from_str(time_part, "%H:%M:%S")
+        .map_err(|e| anyhow!("invalid time {:?} in date_time {:?}: {}", time_part, value, e))?;
+
+    offset
+        .from_local_datetime(&NaiveDateTime::new(naive_date, naive_time))
+        .single()
+        .ok_or_else(|| anyhow!("ambiguous or invalid local datetime in date_time {:?}", value))
+}
+
 pub struct Api<T: Store> {
     session: Arc<Session<T>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl<T: Store> Api<T> {
     pub fn new(session: Arc<Session<T>>) -> Self {
-        Self { session }
+        Self { session, rate_limiter: None }
+    }
+
+    /// Attaches a [`RateLimiter`] so every request issued by this `Api`
+    /// waits for a free slot before it funnels through `Session::send`.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Waits for a free slot on the configured [`RateLimiter`], if any.
+    /// Called at the top of every method below, before `Session::send`.
+    async fn throttle(&self) -> Result<()> {
+        match &self.rate_limiter {
+            Some(limiter) => limiter.acquire().await.map_err(|e| anyhow!(e.to_string())),
+            None => Ok(()),
+        }
     }
 
     /// Fetches quote information for one or more symbols.
@@ -46,6 +115,7 @@ impl<T: Store> Api<T> {
         if symbols.len() > 25 {
             return Err(anyhow!("Maximum of 25 symbols allowed"));
         }
+        self.throttle().await?;
         let val: serde_json::Value = self.session
             .send(
                 Method::GET,
@@ -64,6 +134,7 @@ impl<T: Store> Api<T> {
         params: Option<GetOptionExpireDatesRequest>,
         callbacks: impl CallbackProvider,
     ) -> Result<OptionExpireDateResponse> {
+        self.throttle().await?;
         let val: serde_json::Value = self.session
             .send(
                 Method::GET,
@@ -74,6 +145,25 @@ impl<T: Store> Api<T> {
             .await?;
         Ok(serde_json::from_value(val.get("OptionExpireDateResponse").unwrap().clone())?)
     }
+
+    /// Fetches the full option chain (calls and puts, with greeks) for a given symbol.
+    pub async fn option_chains(
+        &self,
+        params: Option<GetOptionChainsRequest>,
+        callbacks: impl CallbackProvider,
+    ) -> Result<OptionChainResponse> {
+        self.throttle().await?;
+        let val: serde_json::Value = self.session
+            .send(
+                Method::GET,
+                format!("/v1/market/optionchains"),
+                qs_params(&params.unwrap_or_default())?,
+                callbacks,
+            )
+            .await?;
+        debug!("option_chains: {}", val.to_string());
+        Ok(serde_json::from_value(val.get("OptionChainResponse").unwrap().clone())?)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -137,6 +227,261 @@ pub struct QuoteResponse {
     pub messages: Messages,
 }
 
+/// Column order written by [`QuoteResponse::to_csv`] and expected by
+/// [`QuoteResponse::from_csv`].
+const QUOTE_CSV_HEADERS: &[&str] = &[
+    "symbol",
+    "quoteType",
+    "companyName",
+    "lastTrade",
+    "bid",
+    "ask",
+    "volume",
+    "delta",
+    "gamma",
+    "theta",
+    "vega",
+    "rho",
+    "impliedVolatility",
+    "dateTimeUtc",
+    "quoteStatus",
+];
+
+/// Discriminates which nested bucket a CSV row's greeks/price fields came
+/// from, so `from_csv` can reconstruct the right variant instead of guessing
+/// from which optional fields happen to be populated (e.g. an option quote
+/// whose `OptionGreeks.rho` wasn't reported by the chain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteCsvType {
+    Equity,
+    Option,
+}
+
+impl QuoteCsvType {
+    fn as_str(self) -> &'static str {
+        match self {
+            QuoteCsvType::Equity => "EQUITY",
+            QuoteCsvType::Option => "OPTION",
+        }
+    }
+}
+
+impl std::str::FromStr for QuoteCsvType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "EQUITY" => Ok(QuoteCsvType::Equity),
+            "OPTION" => Ok(QuoteCsvType::Option),
+            other => Err(anyhow!("unrecognized quoteType CSV field {:?}", other)),
+        }
+    }
+}
+
+impl QuoteResponse {
+    /// Flattens every `QuoteData` entry into a wide CSV row (symbol, company
+    /// name, last/bid/ask, volume, greeks, timestamp, status), one row per
+    /// symbol, with empty cells for fields that aren't present.
+    pub fn to_csv<W: IoWrite>(&self, w: W) -> Result<()> {
+        let mut writer = Writer::from_writer(w);
+        writer.write_record(QUOTE_CSV_HEADERS)?;
+        for quote in &self.quote_data {
+            writer.write_record(quote.to_csv_record())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Parses CSV produced by [`QuoteResponse::to_csv`] back into
+    /// `QuoteData` entries, for round-tripping saved snapshots. Fields that
+    /// aren't captured by the CSV columns are not reconstructed, and neither
+    /// is `Product`: the `symbol` column is written but not read back.
+    pub fn from_csv<R: Read>(r: R) -> Result<Vec<QuoteData>> {
+        let mut reader = Reader::from_reader(r);
+        reader
+            .records()
+            .map(|record| QuoteData::from_csv_record(&record?))
+            .collect()
+    }
+}
+
+/// Greeks for a single CSV row, carried as a struct rather than a long tuple.
+#[derive(Debug, Clone, Copy, Default)]
+struct Greeks {
+    delta: Option<f64>,
+    gamma: Option<f64>,
+    theta: Option<f64>,
+    vega: Option<f64>,
+    rho: Option<f64>,
+    iv: Option<f64>,
+}
+
+impl QuoteData {
+    /// `dateTimeUTC` as a proper timestamp.
+    pub fn date_time_utc_dt(&self) -> Option<DateTime<Utc>> {
+        self.date_time_utc.and_then(epoch_seconds_to_utc)
+    }
+
+    /// Parses the unparsed `dateTime` string (e.g.
+    /// `"12:00:00 EDT 01-01-2024"`) into a timezone-aware timestamp.
+    pub fn date_time_dt(&self) -> Option<Result<DateTime<FixedOffset>>> {
+        self.date_time.as_deref().map(parse_etrade_date_time)
+    }
+
+    pub(crate) fn symbol(&self) -> Option<String> {
+        self.product
+            .as_ref()
+            .and_then(|p| serde_json::to_value(p).ok())
+            .and_then(|v| v.get("symbol").and_then(|s| s.as_str().map(str::to_string)))
+    }
+
+    fn company_name(&self) -> Option<String> {
+        self.all.as_ref().and_then(|a| a.company_name.clone())
+            .or_else(|| self.fundamental.as_ref().and_then(|f| f.company_name.clone()))
+            .or_else(|| self.intraday.as_ref().and_then(|i| i.company_name.clone()))
+            .or_else(|| self.week52.as_ref().and_then(|w| w.company_name.clone()))
+    }
+
+    fn bid(&self) -> Option<f64> {
+        self.all.as_ref().and_then(|a| a.bid)
+            .or_else(|| self.intraday.as_ref().and_then(|i| i.bid))
+            .or_else(|| self.option.as_ref().and_then(|o| o.bid))
+    }
+
+    fn ask(&self) -> Option<f64> {
+        self.all.as_ref().and_then(|a| a.ask)
+            .or_else(|| self.intraday.as_ref().and_then(|i| i.ask))
+            .or_else(|| self.option.as_ref().and_then(|o| o.ask))
+    }
+
+    fn last_trade(&self) -> Option<f64> {
+        self.all.as_ref().and_then(|a| a.last_trade)
+            .or_else(|| self.intraday.as_ref().and_then(|i| i.last_trade))
+            .or_else(|| self.option.as_ref().and_then(|o| o.last_trade))
+            .or_else(|| self.fundamental.as_ref().and_then(|f| f.last_trade))
+            .or_else(|| self.week52.as_ref().and_then(|w| w.last_trade))
+    }
+
+    fn volume(&self) -> Option<i64> {
+        self.all.as_ref().and_then(|a| a.total_volume)
+            .or_else(|| self.intraday.as_ref().and_then(|i| i.total_volume))
+            .or_else(|| self.week52.as_ref().and_then(|w| w.total_volume))
+    }
+
+    /// Greeks, preferring option-chain greeks (which include rho) over the
+    /// abbreviated set on `All`.
+    fn greeks(&self) -> Greeks {
+        if let Some(g) = self.option.as_ref().and_then(|o| o.option_greeks.as_ref()) {
+            Greeks { delta: g.delta, gamma: g.gamma, theta: g.theta, vega: g.vega, rho: g.rho, iv: g.iv }
+        } else if let Some(a) = &self.all {
+            Greeks { delta: a.delta, gamma: a.gamma, theta: a.theta, vega: a.vega, rho: None, iv: a.implied_volatility }
+        } else {
+            Greeks::default()
+        }
+    }
+
+    /// Which nested bucket (`option` or `all`) this quote's price/greeks
+    /// data is carried in.
+    fn csv_type(&self) -> QuoteCsvType {
+        if self.option.is_some() {
+            QuoteCsvType::Option
+        } else {
+            QuoteCsvType::Equity
+        }
+    }
+
+    fn to_csv_record(&self) -> Vec<String> {
+        fn cell(v: Option<f64>) -> String {
+            v.map(|x| x.to_string()).unwrap_or_default()
+        }
+
+        let greeks = self.greeks();
+        vec![
+            self.symbol().unwrap_or_default(),
+            self.csv_type().as_str().to_string(),
+            self.company_name().unwrap_or_default(),
+            cell(self.last_trade()),
+            cell(self.bid()),
+            cell(self.ask()),
+            self.volume().map(|v| v.to_string()).unwrap_or_default(),
+            cell(greeks.delta),
+            cell(greeks.gamma),
+            cell(greeks.theta),
+            cell(greeks.vega),
+            cell(greeks.rho),
+            cell(greeks.iv),
+            self.date_time_utc.map(|v| v.to_string()).unwrap_or_default(),
+            self.quote_status.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn from_csv_record(record: &StringRecord) -> Result<Self> {
+        fn field(record: &StringRecord, i: usize) -> Option<&str> {
+            record.get(i).filter(|s| !s.is_empty())
+        }
+        fn parse_f64(record: &StringRecord, i: usize, name: &str) -> Result<Option<f64>> {
+            field(record, i)
+                .map(|s| s.parse::<f64>().map_err(|e| anyhow!("invalid {} CSV field {:?}: {}", name, s, e)))
+                .transpose()
+        }
+
+        let quote_type = field(record, 1)
+            .map(str::parse::<QuoteCsvType>)
+            .transpose()?
+            .unwrap_or(QuoteCsvType::Equity);
+        let company_name = field(record, 2).map(str::to_string);
+        let last_trade = parse_f64(record, 3, "lastTrade")?;
+        let bid = parse_f64(record, 4, "bid")?;
+        let ask = parse_f64(record, 5, "ask")?;
+        let total_volume = field(record, 6)
+            .map(|s| s.parse::<i64>().map_err(|e| anyhow!("invalid volume CSV field {:?}: {}", s, e)))
+            .transpose()?;
+        let delta = parse_f64(record, 7, "delta")?;
+        let gamma = parse_f64(record, 8, "gamma")?;
+        let theta = parse_f64(record, 9, "theta")?;
+        let vega = parse_f64(record, 10, "vega")?;
+        let rho = parse_f64(record, 11, "rho")?;
+        let iv = parse_f64(record, 12, "impliedVolatility")?;
+        let date_time_utc = field(record, 13)
+            .map(|s| s.parse::<i64>().map_err(|e| anyhow!("invalid dateTimeUtc CSV field {:?}: {}", s, e)))
+            .transpose()?;
+        let quote_status = field(record, 14).map(str::to_string);
+
+        let (all, option) = if quote_type == QuoteCsvType::Option {
+            (
+                None,
+                Some(OptionQuoteDetails {
+                    ask,
+                    bid,
+                    last_trade,
+                    company_name: company_name.clone(),
+                    option_greeks: Some(OptionGreeks { rho, vega, theta, delta, gamma, iv, ..Default::default() }),
+                    ..Default::default()
+                }),
+            )
+        } else {
+            (
+                Some(AllQuoteDetails {
+                    ask,
+                    bid,
+                    last_trade,
+                    total_volume,
+                    company_name,
+                    delta,
+                    gamma,
+                    theta,
+                    vega,
+                    implied_volatility: iv,
+                    ..Default::default()
+                }),
+                None,
+            )
+        };
+
+        Ok(QuoteData { all, option, date_time_utc, quote_status, ..Default::default() })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct OptionExpireDateResponse {
@@ -155,6 +500,78 @@ pub struct ExpirationDate {
     pub expiry_type: Option<String>,
 }
 
+impl ExpirationDate {
+    /// Returns `None` if `year`/`month`/`day` don't form a valid calendar
+    /// date, e.g. a partial or error entry that deserialized with the
+    /// `#[serde(default)]` zeros.
+    pub fn as_naive_date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month as u32, self.day as u32)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetOptionChainsRequest {
+    pub symbol: String,
+    pub expiry_year: Option<i32>,
+    pub expiry_month: Option<i32>,
+    pub expiry_day: Option<i32>,
+    pub strike_price_near: Option<f64>,
+    pub no_of_strikes: Option<i32>,
+    pub include_weekly: Option<bool>,
+    pub skip_adjusted: Option<bool>,
+    pub chain_type: Option<ChainType>,
+    pub price_type: Option<PriceType>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ChainType {
+    Call,
+    Put,
+    CallPut,
+}
+
+impl Default for ChainType {
+    fn default() -> Self {
+        ChainType::CallPut
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PriceType {
+    Atnm,
+    All,
+}
+
+impl Default for PriceType {
+    fn default() -> Self {
+        PriceType::Atnm
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionChainResponse {
+    #[serde(rename = "OptionPair", skip_serializing_if = "Vec::is_empty")]
+    pub option_pairs: Vec<OptionPair>,
+    pub time_stamp: Option<i64>,
+    pub quote_type: Option<String>,
+    pub near_price: Option<f64>,
+    #[serde(skip_serializing_if = "Messages::is_empty")]
+    pub messages: Messages,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionPair {
+    #[serde(rename = "Call", skip_serializing_if = "Option::is_none")]
+    pub call: Option<OptionQuoteDetails>,
+    #[serde(rename = "Put", skip_serializing_if = "Option::is_none")]
+    pub put: Option<OptionQuoteDetails>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct QuoteData {
@@ -216,6 +633,12 @@ pub struct AllQuoteDetails {
     pub implied_volatility: Option<f64>,
 }
 
+impl AllQuoteDetails {
+    pub fn expiration_date_dt(&self) -> Option<DateTime<Utc>> {
+        self.expiration_date.and_then(epoch_seconds_to_utc)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct FundamentalQuoteDetails {
@@ -266,6 +689,109 @@ pub struct OptionQuoteDetails {
     pub option_greeks: Option<OptionGreeks>,
 }
 
+impl OptionQuoteDetails {
+    /// Parses `osi_key` into a structured [`OptionSymbol`].
+    pub fn osi_symbol(&self) -> Result<OptionSymbol> {
+        let osi_key = self
+            .osi_key
+            .as_deref()
+            .ok_or_else(|| anyhow!("osi_key is not present on this option quote"))?;
+        OptionSymbol::parse(osi_key)
+    }
+}
+
+/// Whether an [`OptionSymbol`] identifies a call or a put contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// A parsed OSI-standard 21-character option symbol, e.g. `"AAPL  240119C00150000"`.
+///
+/// The format is: 6-character underlying root (space-padded, left-justified),
+/// 6-digit expiration date as `YYMMDD`, a single `C`/`P` type character, and an
+/// 8-digit strike price expressed as dollars × 1000.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionSymbol {
+    underlying: String,
+    expiration_date: NaiveDate,
+    option_type: OptionType,
+    strike_thousandths: i64,
+}
+
+impl OptionSymbol {
+    pub fn parse(osi_key: &str) -> Result<Self> {
+        if !osi_key.is_ascii() || osi_key.len() != 21 {
+            return Err(anyhow!(
+                "OSI option symbol must be 21 ASCII characters, got {:?}",
+                osi_key
+            ));
+        }
+
+        let underlying = osi_key[0..6].trim_end().to_string();
+        let date_part = &osi_key[6..12];
+        let type_part = &osi_key[12..13];
+        let strike_part = &osi_key[13..21];
+
+        let expiration_date = NaiveDate::parse_from_str(date_part, "%y%m%d")
+            .map_err(|e| anyhow!("invalid OSI expiration date {:?}: {}", date_part, e))?;
+
+        let option_type = match type_part {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            other => return Err(anyhow!("invalid OSI option type {:?}", other)),
+        };
+
+        if !strike_part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(anyhow!("invalid OSI strike price {:?}", strike_part));
+        }
+        let strike_thousandths: i64 = strike_part
+            .parse()
+            .map_err(|_| anyhow!("invalid OSI strike price {:?}", strike_part))?;
+
+        Ok(Self {
+            underlying,
+            expiration_date,
+            option_type,
+            strike_thousandths,
+        })
+    }
+
+    pub fn underlying(&self) -> &str {
+        &self.underlying
+    }
+
+    pub fn expiration_date(&self) -> NaiveDate {
+        self.expiration_date
+    }
+
+    pub fn option_type(&self) -> OptionType {
+        self.option_type
+    }
+
+    pub fn strike(&self) -> f64 {
+        self.strike_thousandths as f64 / 1000.0
+    }
+}
+
+impl std::fmt::Display for OptionSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let type_char = match self.option_type {
+            OptionType::Call => 'C',
+            OptionType::Put => 'P',
+        };
+        write!(
+            f,
+            "{:<6}{}{}{:08}",
+            self.underlying,
+            self.expiration_date.format("%y%m%d"),
+            type_char,
+            self.strike_thousandths
+        )
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct OptionGreeks {
@@ -373,6 +899,28 @@ pub struct MutualFund {
     pub exchange_code: Option<String>,
 }
 
+impl MutualFund {
+    pub fn time_of_last_trade_dt(&self) -> Option<DateTime<Utc>> {
+        self.time_of_last_trade.and_then(epoch_seconds_to_utc)
+    }
+
+    pub fn order_cutoff_time_dt(&self) -> Option<DateTime<Utc>> {
+        self.order_cutoff_time.and_then(epoch_seconds_to_utc)
+    }
+
+    pub fn fund_inception_date_dt(&self) -> Option<DateTime<Utc>> {
+        self.fund_inception_date.and_then(epoch_seconds_to_utc)
+    }
+
+    pub fn week_52_low_date_dt(&self) -> Option<DateTime<Utc>> {
+        self.week_52_low_date.and_then(epoch_seconds_to_utc)
+    }
+
+    pub fn week_52_hi_date_dt(&self) -> Option<DateTime<Utc>> {
+        self.week_52_hi_date.and_then(epoch_seconds_to_utc)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct NetAsset {
@@ -380,6 +928,12 @@ pub struct NetAsset {
     pub as_of_date: Option<i64>,
 }
 
+impl NetAsset {
+    pub fn as_of_date_dt(&self) -> Option<DateTime<Utc>> {
+        self.as_of_date.and_then(epoch_seconds_to_utc)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct Redemption {
@@ -508,4 +1062,189 @@ mod test {
         assert_eq!(quote_response.quote_data.len(), 1);
         assert!(quote_response.messages.is_empty());
     }
+
+    #[test]
+    fn test_option_chain_response_deserialization() {
+        let json = r#"{
+            "OptionChainResponse": {
+                "OptionPair": [
+                    {
+                        "Call": {
+                            "bid": 1.5,
+                            "ask": 1.6,
+                            "osiKey": "AAPL  240119C00150000",
+                            "OptionGreeks": {
+                                "delta": 0.55,
+                                "gamma": 0.02
+                            }
+                        },
+                        "Put": {
+                            "bid": 0.9,
+                            "ask": 1.0,
+                            "osiKey": "AAPL  240119P00150000"
+                        }
+                    }
+                ],
+                "quoteType": "DELAYED",
+                "nearPrice": 150.25
+            }
+        }"#;
+
+        let json_value: serde_json::Value = serde_json::from_str(json)
+            .expect("Failed to parse option chain JSON");
+
+        let option_chain_value = json_value.get("OptionChainResponse")
+            .expect("No OptionChainResponse field in test data")
+            .clone();
+
+        let response: OptionChainResponse = serde_json::from_value(option_chain_value)
+            .expect("Failed to deserialize OptionChainResponse");
+
+        assert_eq!(response.option_pairs.len(), 1);
+        let pair = &response.option_pairs[0];
+        assert_eq!(pair.call.as_ref().unwrap().bid, Some(1.5));
+        assert_eq!(pair.put.as_ref().unwrap().ask, Some(1.0));
+        assert_eq!(
+            pair.call.as_ref().unwrap().option_greeks.as_ref().unwrap().delta,
+            Some(0.55)
+        );
+    }
+
+    #[test]
+    fn test_option_symbol_parse_roundtrip() {
+        let symbol = OptionSymbol::parse("AAPL  240119C00150000").expect("should parse");
+
+        assert_eq!(symbol.underlying(), "AAPL");
+        assert_eq!(symbol.expiration_date(), NaiveDate::from_ymd_opt(2024, 1, 19).unwrap());
+        assert_eq!(symbol.option_type(), OptionType::Call);
+        assert_eq!(symbol.strike(), 150.0);
+        assert_eq!(symbol.to_string(), "AAPL  240119C00150000");
+    }
+
+    #[test]
+    fn test_option_symbol_parse_short_root_pads_on_display() {
+        let symbol = OptionSymbol::parse("F     240119P00010500").expect("should parse");
+
+        assert_eq!(symbol.underlying(), "F");
+        assert_eq!(symbol.option_type(), OptionType::Put);
+        assert_eq!(symbol.strike(), 10.5);
+        assert_eq!(symbol.to_string(), "F     240119P00010500");
+    }
+
+    #[test]
+    fn test_option_symbol_parse_wrong_length_errors() {
+        assert!(OptionSymbol::parse("AAPL240119C00150000").is_err());
+    }
+
+    #[test]
+    fn test_option_symbol_parse_bad_strike_errors() {
+        assert!(OptionSymbol::parse("AAPL  240119CXXXXXXXX").is_err());
+    }
+
+    #[test]
+    fn test_quote_response_csv_roundtrip() {
+        let response = QuoteResponse {
+            quote_data: vec![QuoteData {
+                all: Some(AllQuoteDetails {
+                    company_name: Some("TEST COMPANY".to_string()),
+                    last_trade: Some(100.0),
+                    bid: Some(99.5),
+                    ask: Some(100.5),
+                    total_volume: Some(12345),
+                    delta: Some(0.5),
+                    ..Default::default()
+                }),
+                date_time_utc: Some(1704067200),
+                quote_status: Some("REALTIME".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        response.to_csv(&mut buf).expect("should write csv");
+        let csv_text = String::from_utf8(buf.clone()).unwrap();
+        assert!(csv_text.starts_with("symbol,quoteType,companyName,lastTrade"));
+
+        let parsed = QuoteResponse::from_csv(buf.as_slice()).expect("should parse csv");
+        assert_eq!(parsed.len(), 1);
+        let all = parsed[0].all.as_ref().expect("should have All details");
+        assert_eq!(all.company_name.as_deref(), Some("TEST COMPANY"));
+        assert_eq!(all.last_trade, Some(100.0));
+        assert_eq!(all.bid, Some(99.5));
+        assert_eq!(all.total_volume, Some(12345));
+        assert_eq!(all.delta, Some(0.5));
+        assert_eq!(parsed[0].date_time_utc, Some(1704067200));
+    }
+
+    #[test]
+    fn test_quote_response_csv_roundtrip_option_without_rho() {
+        // A real option-chain quote where the chain didn't report rho must
+        // still round-trip as an option quote, not get silently
+        // reclassified as an equity quote.
+        let response = QuoteResponse {
+            quote_data: vec![QuoteData {
+                option: Some(OptionQuoteDetails {
+                    bid: Some(1.5),
+                    ask: Some(1.6),
+                    osi_key: Some("AAPL  240119C00150000".to_string()),
+                    option_greeks: Some(OptionGreeks {
+                        delta: Some(0.55),
+                        gamma: Some(0.02),
+                        rho: None,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        response.to_csv(&mut buf).expect("should write csv");
+
+        let parsed = QuoteResponse::from_csv(buf.as_slice()).expect("should parse csv");
+        assert_eq!(parsed.len(), 1);
+        assert!(parsed[0].all.is_none());
+        let option = parsed[0].option.as_ref().expect("should have Option details, not All");
+        assert_eq!(option.bid, Some(1.5));
+        assert_eq!(option.ask, Some(1.6));
+        let greeks = option.option_greeks.as_ref().expect("should have OptionGreeks");
+        assert_eq!(greeks.delta, Some(0.55));
+        assert_eq!(greeks.gamma, Some(0.02));
+        assert_eq!(greeks.rho, None);
+    }
+
+    #[test]
+    fn test_date_time_utc_dt() {
+        let quote = QuoteData { date_time_utc: Some(1704067200), ..Default::default() };
+        let dt = quote.date_time_utc_dt().expect("should convert epoch seconds");
+        assert_eq!(dt.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_etrade_date_time() {
+        let dt = parse_etrade_date_time("12:00:00 EDT 01-01-2024").expect("should parse");
+        assert_eq!(dt.to_rfc3339(), "2024-01-01T12:00:00-04:00");
+    }
+
+    #[test]
+    fn test_parse_etrade_date_time_unknown_timezone_errors() {
+        assert!(parse_etrade_date_time("12:00:00 ZZZ 01-01-2024").is_err());
+    }
+
+    #[test]
+    fn test_expiration_date_as_naive_date() {
+        let expiration = ExpirationDate { year: 2024, month: 1, day: 19, expiry_type: None };
+        assert_eq!(expiration.as_naive_date(), NaiveDate::from_ymd_opt(2024, 1, 19));
+    }
+
+    #[test]
+    fn test_expiration_date_as_naive_date_invalid_is_none() {
+        // A partial/error entry that deserialized with the `#[serde(default)]`
+        // zeros rather than a real date.
+        let expiration = ExpirationDate { year: 0, month: 0, day: 0, expiry_type: None };
+        assert_eq!(expiration.as_naive_date(), None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,170 @@
+//! Polling-based quote subscriptions.
+//!
+//! E*TRADE has no market-data push socket, so `subscribe_quotes` re-polls
+//! [`Api::quote`](crate::market::Api::quote) on an interval and yields a
+//! [`futures::Stream`] of the ticks that actually changed, batching requests
+//! to the existing 25-symbol-per-call limit. Every poll is just a regular
+//! `Api::quote` call, so if that `Api` was built with
+//! [`Api::with_rate_limiter`](crate::market::Api::with_rate_limiter), polls
+//! issued here are throttled exactly like any other one-shot call.
+
+use crate::market::{Api, GetQuotesRequest, QuoteData};
+use crate::{CallbackProvider, Store};
+use anyhow::Result;
+use futures::stream::Stream;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::interval;
+
+/// A handle for adding or removing symbols from a live [`subscribe_quotes`]
+/// stream without tearing it down.
+#[derive(Clone)]
+pub struct QuoteStreamHandle {
+    symbols: Arc<Mutex<HashSet<String>>>,
+}
+
+impl QuoteStreamHandle {
+    pub async fn add_symbol(&self, symbol: impl Into<String>) {
+        self.symbols.lock().await.insert(symbol.into());
+    }
+
+    pub async fn remove_symbol(&self, symbol: &str) {
+        self.symbols.lock().await.remove(symbol);
+    }
+
+    pub async fn symbols(&self) -> Vec<String> {
+        self.symbols.lock().await.iter().cloned().collect()
+    }
+}
+
+const MAX_SYMBOLS_PER_REQUEST: usize = 25;
+
+/// Subscribes to quotes for `initial_symbols`, re-polling every
+/// `poll_interval`. Returns a stream of changed ticks alongside a
+/// [`QuoteStreamHandle`] for adjusting the symbol set while the stream runs.
+pub fn subscribe_quotes<T, C>(
+    api: Arc<Api<T>>,
+    initial_symbols: impl IntoIterator<Item = String>,
+    poll_interval: Duration,
+    params: Option<GetQuotesRequest>,
+    callbacks: C,
+) -> (impl Stream<Item = Result<QuoteData>>, QuoteStreamHandle)
+where
+    T: Store,
+    C: CallbackProvider + Clone + Send + Sync + 'static,
+{
+    let symbols = Arc::new(Mutex::new(initial_symbols.into_iter().collect::<HashSet<_>>()));
+    let handle = QuoteStreamHandle { symbols: symbols.clone() };
+
+    let state = State {
+        api,
+        symbols: symbols.clone(),
+        params,
+        callbacks,
+        last_seen: HashMap::new(),
+        pending: VecDeque::new(),
+        ticker: interval(poll_interval),
+    };
+
+    let stream = futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+
+            state.ticker.tick().await;
+
+            let symbols: Vec<String> = {
+                let guard = state.symbols.lock().await;
+                guard.iter().cloned().collect()
+            };
+            if symbols.is_empty() {
+                continue;
+            }
+
+            for chunk in symbols.chunks(MAX_SYMBOLS_PER_REQUEST) {
+                let refs: Vec<&str> = chunk.iter().map(String::as_str).collect();
+                match state.api.quote(&refs, state.params.clone(), state.callbacks.clone()).await {
+                    Ok(response) => {
+                        for quote in response.quote_data.into_iter() {
+                            // Match each returned quote back to its own symbol rather than
+                            // assuming the response preserves request order.
+                            let symbol = quote.symbol().unwrap_or_default();
+                            let changed = is_new_tick(&state.last_seen, &symbol, quote.date_time_utc);
+                            if let Some(now) = quote.date_time_utc {
+                                state.last_seen.insert(symbol, now);
+                            }
+                            if changed {
+                                state.pending.push_back(Ok(quote));
+                            }
+                        }
+                    }
+                    Err(e) => state.pending.push_back(Err(e)),
+                }
+            }
+        }
+    });
+
+    (stream, handle)
+}
+
+struct State<T: Store, C: CallbackProvider + Clone> {
+    api: Arc<Api<T>>,
+    symbols: Arc<Mutex<HashSet<String>>>,
+    params: Option<GetQuotesRequest>,
+    callbacks: C,
+    last_seen: HashMap<String, i64>,
+    pending: VecDeque<Result<QuoteData>>,
+    ticker: tokio::time::Interval,
+}
+
+/// The change-detection predicate behind `subscribe_quotes`: a tick is worth
+/// emitting unless we've already seen this exact `date_time_utc` for this
+/// symbol.
+fn is_new_tick(last_seen: &HashMap<String, i64>, symbol: &str, date_time_utc: Option<i64>) -> bool {
+    match (last_seen.get(symbol), date_time_utc) {
+        (Some(&seen), Some(now)) => seen != now,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_new_tick_first_sighting_is_always_new() {
+        let last_seen = HashMap::new();
+        assert!(is_new_tick(&last_seen, "AAPL", Some(1704067200)));
+    }
+
+    #[test]
+    fn test_is_new_tick_unchanged_timestamp_is_not_new() {
+        let mut last_seen = HashMap::new();
+        last_seen.insert("AAPL".to_string(), 1704067200);
+        assert!(!is_new_tick(&last_seen, "AAPL", Some(1704067200)));
+    }
+
+    #[test]
+    fn test_is_new_tick_changed_timestamp_is_new() {
+        let mut last_seen = HashMap::new();
+        last_seen.insert("AAPL".to_string(), 1704067200);
+        assert!(is_new_tick(&last_seen, "AAPL", Some(1704067260)));
+    }
+
+    #[test]
+    fn test_is_new_tick_missing_timestamp_is_always_new() {
+        let mut last_seen = HashMap::new();
+        last_seen.insert("AAPL".to_string(), 1704067200);
+        assert!(is_new_tick(&last_seen, "AAPL", None));
+    }
+
+    #[test]
+    fn test_is_new_tick_distinguishes_symbols() {
+        let mut last_seen = HashMap::new();
+        last_seen.insert("AAPL".to_string(), 1704067200);
+        assert!(is_new_tick(&last_seen, "MSFT", Some(1704067200)));
+    }
+}
@@ -0,0 +1,172 @@
+//! Client-side rate limiting for outbound E*TRADE API calls.
+//!
+//! [`RateLimiter`] is consulted via `acquire()` before every request. Attach
+//! one to a market `Api` with `Api::with_rate_limiter` and it throttles
+//! `quote()`, `option_expire_dates()`, and `option_chains()` alike, so
+//! bursts across those calls can't trip the market data service's 429s.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{Instant, sleep};
+
+/// A single rate-limit window: at most `max_requests` requests per rolling
+/// `interval`, each request counting as one.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub interval: Duration,
+    pub max_requests: u32,
+}
+
+impl RateLimit {
+    pub fn new(interval: Duration, max_requests: u32) -> Self {
+        Self { interval, max_requests }
+    }
+}
+
+/// Returned by [`RateLimiter::acquire`] when the wait for a free slot would
+/// exceed the configured maximum backoff.
+#[derive(Debug)]
+pub struct RateLimited {
+    /// Total wait the next free slot would need: time already spent waiting
+    /// plus the remaining wait, which `acquire` does not sleep through.
+    pub waited: Duration,
+    pub max_wait: Duration,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rate limited: a free slot needs {:?} of waiting (max backoff is {:?})",
+            self.waited, self.max_wait
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    timestamps: VecDeque<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self { limit, timestamps: VecDeque::new() }
+    }
+
+    /// Evicts timestamps that have aged out of the window and returns how
+    /// long the caller must still wait for a slot, if any.
+    fn wait_for_slot(&mut self, now: Instant) -> Option<Duration> {
+        while let Some(&oldest) = self.timestamps.front() {
+            if now.saturating_duration_since(oldest) >= self.limit.interval {
+                self.timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if (self.timestamps.len() as u32) < self.limit.max_requests {
+            None
+        } else {
+            let oldest = *self.timestamps.front().expect("bucket at capacity has a front");
+            Some(self.limit.interval - now.saturating_duration_since(oldest))
+        }
+    }
+
+    fn record(&mut self, now: Instant) {
+        self.timestamps.push_back(now);
+    }
+}
+
+/// Consulted via `acquire()` before every request. A request is only let
+/// through once every configured [`RateLimit`] bucket has room; otherwise
+/// the caller waits for the nearest slot, up to `max_wait` in total, after
+/// which [`RateLimited`] is returned instead of blocking indefinitely.
+///
+/// Share one limiter (wrapped in an `Arc`) across every `Api` built on top
+/// of the same underlying session so they all observe the same limits.
+#[derive(Debug)]
+pub struct RateLimiter {
+    max_wait: Duration,
+    buckets: Mutex<Vec<Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: impl IntoIterator<Item = RateLimit>, max_wait: Duration) -> Self {
+        Self {
+            max_wait,
+            buckets: Mutex::new(limits.into_iter().map(Bucket::new).collect()),
+        }
+    }
+
+    /// Waits until every bucket has room for one more request, then records
+    /// it against all of them. Returns `Err(RateLimited)` once the total wait
+    /// would exceed `max_wait`.
+    pub async fn acquire(&self) -> Result<(), RateLimited> {
+        let mut waited = Duration::ZERO;
+
+        loop {
+            let now = Instant::now();
+            let mut buckets = self.buckets.lock().await;
+            let next_wait = buckets.iter_mut().filter_map(|b| b.wait_for_slot(now)).max();
+
+            let Some(wait) = next_wait else {
+                for bucket in buckets.iter_mut() {
+                    bucket.record(now);
+                }
+                return Ok(());
+            };
+            drop(buckets);
+
+            if waited + wait > self.max_wait {
+                return Err(RateLimited { waited: waited + wait, max_wait: self.max_wait });
+            }
+            sleep(wait).await;
+            waited += wait;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_allows_requests_within_limit() {
+        let limiter = RateLimiter::new(
+            vec![RateLimit::new(Duration::from_secs(60), 2)],
+            Duration::from_secs(5),
+        );
+
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_then_succeeds_once_window_passes() {
+        let limiter = RateLimiter::new(
+            vec![RateLimit::new(Duration::from_millis(50), 1)],
+            Duration::from_secs(5),
+        );
+
+        assert!(limiter.acquire().await.is_ok());
+        // A second call must wait ~50ms for the bucket to free up, but should
+        // still succeed since that's well under max_wait.
+        assert!(limiter.acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_errors_past_max_wait() {
+        let limiter = RateLimiter::new(
+            vec![RateLimit::new(Duration::from_secs(60), 1)],
+            Duration::from_millis(10),
+        );
+
+        assert!(limiter.acquire().await.is_ok());
+        let err = limiter.acquire().await.unwrap_err();
+        assert!(err.waited > Duration::from_millis(10));
+    }
+}